@@ -0,0 +1,92 @@
+//! Throughput benchmarks for the `Vector3` hot paths: `dot`, `length`,
+//! `Add`, and `MulAssign`.
+//!
+//! Each benchmark is written generically over the scalar type, so another
+//! scalar type is measured through the same harness by adding another
+//! `bench_scalar::<T>(...)` call below. `bench_scalar` requires `T: Scalar +
+//! MulAssign`; `Fixed<I, B>` and `F16` do not implement `MulAssign`, so only
+//! `f32` and `f64` are benchmarked here.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::rngs::ThreadRng;
+use rand::Rng;
+
+use rust_pbrt::core::geometry::{Scalar, Vector3};
+
+const SAMPLE_SIZE: usize = 10_000;
+
+fn random_vectors<T: Scalar>(sample: &dyn Fn(&mut ThreadRng) -> T, count: usize) -> Vec<Vector3<T>> {
+    let mut rng = rand::thread_rng();
+    (0..count)
+        .map(|_| Vector3::new(sample(&mut rng), sample(&mut rng), sample(&mut rng)))
+        .collect()
+}
+
+fn bench_dot<T: Scalar>(c: &mut Criterion, label: &str, sample: &dyn Fn(&mut ThreadRng) -> T) {
+    let vectors = random_vectors(sample, SAMPLE_SIZE);
+    c.bench_function(&format!("vector3_dot/{label}"), |b| {
+        b.iter(|| {
+            for pair in vectors.chunks_exact(2) {
+                black_box(pair[0].dot(pair[1]));
+            }
+        })
+    });
+}
+
+fn bench_length<T: Scalar>(c: &mut Criterion, label: &str, sample: &dyn Fn(&mut ThreadRng) -> T) {
+    let vectors = random_vectors(sample, SAMPLE_SIZE);
+    c.bench_function(&format!("vector3_length/{label}"), |b| {
+        b.iter(|| {
+            for v in &vectors {
+                black_box(v.length());
+            }
+        })
+    });
+}
+
+fn bench_add<T: Scalar>(c: &mut Criterion, label: &str, sample: &dyn Fn(&mut ThreadRng) -> T) {
+    let vectors = random_vectors(sample, SAMPLE_SIZE);
+    c.bench_function(&format!("vector3_add/{label}"), |b| {
+        b.iter(|| {
+            for pair in vectors.chunks_exact(2) {
+                black_box(pair[0] + pair[1]);
+            }
+        })
+    });
+}
+
+fn bench_mul_assign<T: Scalar + std::ops::MulAssign>(
+    c: &mut Criterion,
+    label: &str,
+    sample: &dyn Fn(&mut ThreadRng) -> T,
+) {
+    let mut vectors = random_vectors(sample, SAMPLE_SIZE);
+    let scalar = sample(&mut rand::thread_rng());
+    c.bench_function(&format!("vector3_mul_assign/{label}"), |b| {
+        b.iter(|| {
+            for v in vectors.iter_mut() {
+                *v *= scalar;
+                black_box(&*v);
+            }
+        })
+    });
+}
+
+fn bench_scalar<T: Scalar + std::ops::MulAssign>(
+    c: &mut Criterion,
+    label: &str,
+    sample: impl Fn(&mut ThreadRng) -> T,
+) {
+    bench_dot(c, label, &sample);
+    bench_length(c, label, &sample);
+    bench_add(c, label, &sample);
+    bench_mul_assign(c, label, &sample);
+}
+
+fn geometry_benches(c: &mut Criterion) {
+    bench_scalar::<f32>(c, "f32", |rng| rng.gen_range(-1000.0f32..1000.0));
+    bench_scalar::<f64>(c, "f64", |rng| rng.gen_range(-1000.0f64..1000.0));
+}
+
+criterion_group!(benches, geometry_benches);
+criterion_main!(benches);
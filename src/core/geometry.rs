@@ -3,8 +3,489 @@ use std::ops;
 
 use num_traits::Float;
 
+// Fixed iteration count (not a convergence check) for the Newton loops in Fixed::recip/sqrt.
+const NEWTON_ITERATIONS: u32 = 12;
+
+// Common scalar surface required to build a Vector2/Vector3 over it; f32/f64 get this
+// for free below, Fixed<I, B> implements it directly.
+pub trait Scalar:
+    Copy
+    + ops::Add<Output = Self>
+    + ops::Sub<Output = Self>
+    + ops::Mul<Output = Self>
+    + ops::Div<Output = Self>
+{
+    fn is_nan(self) -> bool;
+    fn sqrt(self) -> Self;
+    fn recip(self) -> Self;
+}
+
+impl<T: Float> Scalar for T {
+    fn is_nan(self) -> bool {
+        Float::is_nan(self)
+    }
+
+    fn sqrt(self) -> Self {
+        Float::sqrt(self)
+    }
+
+    fn recip(self) -> Self {
+        Float::recip(self)
+    }
+}
+
+// Integer type usable as the backing representation of a Fixed scalar. Wide must be at
+// least twice as wide as Self so multiplication/division don't overflow before narrowing.
+pub trait FixedRepr:
+    Copy
+    + PartialEq
+    + PartialOrd
+    + Debug
+    + ops::Add<Output = Self>
+    + ops::Sub<Output = Self>
+    + ops::Shl<u32, Output = Self>
+    + ops::Shr<u32, Output = Self>
+{
+    type Wide: Copy
+        + ops::Mul<Output = Self::Wide>
+        + ops::Div<Output = Self::Wide>
+        + ops::Shl<u32, Output = Self::Wide>
+        + ops::Shr<u32, Output = Self::Wide>;
+
+    const BITS: u32;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn widen(self) -> Self::Wide;
+    fn narrow(wide: Self::Wide) -> Self;
+}
+
+macro_rules! impl_fixed_repr {
+    ($repr:ty, $wide:ty) => {
+        impl FixedRepr for $repr {
+            type Wide = $wide;
+
+            const BITS: u32 = <$repr>::BITS;
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn widen(self) -> Self::Wide {
+                self as Self::Wide
+            }
+
+            fn narrow(wide: Self::Wide) -> Self {
+                wide as Self
+            }
+        }
+    };
+}
+
+impl_fixed_repr!(i16, i32);
+impl_fixed_repr!(i32, i64);
+impl_fixed_repr!(i64, i128);
+impl_fixed_repr!(u16, u32);
+impl_fixed_repr!(u32, u64);
+impl_fixed_repr!(u64, u128);
+
+// Deterministic fixed-point scalar with B fractional bits backed by I. Fixed::from_int(3)
+// is 3.0; arithmetic runs on the raw shifted integer, so it's bit-exact unlike f32/f64.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Fixed<I, const B: u32> {
+    raw: I,
+}
+
+impl<I: FixedRepr, const B: u32> Fixed<I, B> {
+    pub fn from_int(value: I) -> Self {
+        assert!(B < I::BITS, "B must be smaller than the bit-width of I");
+        Fixed { raw: value << B }
+    }
+
+    pub fn raw(self) -> I {
+        self.raw
+    }
+
+    /// Computes `1 / self` via Newton-Raphson iteration (`x_{n+1} = x_n * (2 - self * x_n)`),
+    /// nudging the initial guess of `1.0` into the convergent range first.
+    pub fn recip(self) -> Self {
+        assert!(B < I::BITS, "B must be smaller than the bit-width of I");
+        assert!(self.raw != I::ZERO, "division by zero in Fixed::recip");
+
+        let negative = self.raw < I::ZERO;
+        let magnitude = Fixed { raw: if negative { I::ZERO - self.raw } else { self.raw } };
+
+        let one = Fixed::<I, B>::from_int(I::ONE);
+        let two = one + one;
+
+        // Seed the initial guess so `magnitude * x` falls inside the
+        // Newton-Raphson convergence basin of (0, 2), by halving/doubling a
+        // starting guess of 1.0.
+        let mut x = one;
+        for _ in 0..I::BITS {
+            if magnitude * x >= two {
+                x = Fixed { raw: x.raw >> 1 };
+            } else {
+                break;
+            }
+        }
+        for _ in 0..I::BITS {
+            if magnitude * x < one {
+                x = x + x;
+            } else {
+                break;
+            }
+        }
+
+        for _ in 0..NEWTON_ITERATIONS {
+            x = x * (two - magnitude * x);
+        }
+
+        if negative { Fixed { raw: I::ZERO - x.raw } } else { x }
+    }
+
+    /// Computes `sqrt(self)` via Newton-Raphson iteration
+    /// (`x_{n+1} = (x_n + self / x_n) / 2`), seeded from `self` itself.
+    pub fn sqrt(self) -> Self {
+        assert!(self.raw >= I::ZERO, "sqrt of a negative Fixed value");
+        if self.raw == I::ZERO {
+            return self;
+        }
+
+        let mut x = self;
+        for _ in 0..NEWTON_ITERATIONS {
+            let sum = x + (self / x);
+            x = Fixed { raw: sum.raw >> 1 };
+        }
+        x
+    }
+}
+
+impl<I: FixedRepr, const B: u32> ops::Add for Fixed<I, B> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Fixed { raw: self.raw + rhs.raw }
+    }
+}
+
+impl<I: FixedRepr, const B: u32> ops::Sub for Fixed<I, B> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Fixed { raw: self.raw - rhs.raw }
+    }
+}
+
+impl<I: FixedRepr, const B: u32> ops::Mul for Fixed<I, B> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        assert!(B < I::BITS, "B must be smaller than the bit-width of I");
+        let product = self.raw.widen() * rhs.raw.widen();
+        Fixed { raw: I::narrow(product >> B) }
+    }
+}
+
+impl<I: FixedRepr, const B: u32> ops::Div for Fixed<I, B> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        assert!(B < I::BITS, "B must be smaller than the bit-width of I");
+        assert!(rhs.raw != I::ZERO, "division by zero in Fixed::div");
+        let numerator = self.raw.widen() << B;
+        Fixed { raw: I::narrow(numerator / rhs.raw.widen()) }
+    }
+}
+
+impl<I: FixedRepr, const B: u32> Scalar for Fixed<I, B> {
+    fn is_nan(self) -> bool {
+        false
+    }
+
+    fn sqrt(self) -> Self {
+        Fixed::sqrt(self)
+    }
+
+    fn recip(self) -> Self {
+        Fixed::recip(self)
+    }
+}
+
+#[cfg(test)]
+mod fixed_tests {
+    use std::panic;
+
+    use crate::core::geometry::{Fixed, Scalar, Vector3};
+
+    type F = Fixed<i32, 16>;
+
+    #[test]
+    fn test_from_int() {
+        assert_eq!(F::from_int(3).raw(), 3 << 16);
+        assert_eq!(F::from_int(0).raw(), 0);
+    }
+
+    #[test]
+    fn test_from_int_panics_when_b_too_wide() {
+        let result = panic::catch_unwind(|| Fixed::<i32, 32>::from_int(1));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_add_sub() {
+        assert_eq!(F::from_int(2) + F::from_int(3), F::from_int(5));
+        assert_eq!(F::from_int(5) - F::from_int(3), F::from_int(2));
+    }
+
+    #[test]
+    fn test_mul_div() {
+        assert_eq!(F::from_int(3) * F::from_int(4), F::from_int(12));
+        assert_eq!(F::from_int(12) / F::from_int(4), F::from_int(3));
+    }
+
+    #[test]
+    fn test_recip() {
+        let recip = F::from_int(4).recip();
+        assert!((recip.raw() - (F::from_int(1).raw() / 4)).abs() <= 1);
+    }
+
+    #[test]
+    fn test_recip_negative() {
+        let recip = F::from_int(-4).recip();
+        assert!((recip.raw() - (-(F::from_int(1).raw() / 4))).abs() <= 1);
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(F::from_int(4).sqrt(), F::from_int(2));
+        assert_eq!(F::from_int(0).sqrt(), F::from_int(0));
+    }
+
+    #[test]
+    fn test_is_nan_always_false() {
+        assert!(!F::from_int(1).is_nan());
+    }
+
+    #[test]
+    fn test_vector3_fixed() {
+        let v1 = Vector3::new(F::from_int(3), F::from_int(0), F::from_int(4));
+        assert_eq!(v1.length(), F::from_int(5));
+    }
+}
+
+// Converts self into T, promoting/narrowing as required; symmetric with ConvertFrom so a
+// conversion can be written from either side.
+pub trait ConvertTo<T> {
+    fn convert_to(self) -> T;
+}
+
+pub trait ConvertFrom<T> {
+    fn convert_from(value: T) -> Self;
+}
+
+impl<T, U> ConvertFrom<T> for U where T: ConvertTo<U> {
+    fn convert_from(value: T) -> Self {
+        value.convert_to()
+    }
+}
+
+impl ConvertTo<f64> for f32 {
+    fn convert_to(self) -> f64 {
+        self as f64
+    }
+}
+
+impl ConvertTo<f32> for f64 {
+    fn convert_to(self) -> f32 {
+        self as f32
+    }
+}
+
+// Half-precision scalar for compactly storing directions/colors; backed by half::f16
+// when the f16 feature is enabled, and a raw u16 IEEE-754 bit pattern otherwise.
+#[cfg(feature = "f16")]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct F16(half::f16);
+
+#[cfg(not(feature = "f16"))]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct F16(u16);
+
+#[cfg(not(feature = "f16"))]
+fn f16_bits_to_f32(bits: u16) -> f32 {
+    let sign = (bits >> 15) as u32;
+    let exponent = ((bits >> 10) & 0x1f) as u32;
+    let mantissa = (bits & 0x3ff) as u32;
+
+    let bits32 = if exponent == 0 {
+        if mantissa == 0 {
+            sign << 31
+        } else {
+            // Subnormal half -> normalized single: shift the mantissa up
+            // until its implicit leading bit is set, adjusting the exponent
+            // to match.
+            let mut mantissa = mantissa;
+            let mut unbiased_exponent = 0i32;
+            while mantissa & 0x400 == 0 {
+                mantissa <<= 1;
+                unbiased_exponent -= 1;
+            }
+            mantissa &= 0x3ff;
+            let exponent = (127 - 15 + unbiased_exponent + 1) as u32;
+            (sign << 31) | (exponent << 23) | (mantissa << 13)
+        }
+    } else if exponent == 0x1f {
+        (sign << 31) | (0xff << 23) | (mantissa << 13)
+    } else {
+        let exponent = exponent + (127 - 15);
+        (sign << 31) | (exponent << 23) | (mantissa << 13)
+    };
+
+    f32::from_bits(bits32)
+}
+
+#[cfg(not(feature = "f16"))]
+fn f32_to_f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let f32_exponent = (bits >> 23) & 0xff;
+    let exponent = f32_exponent as i32 - 127 + 15;
+    let mantissa = bits & 0x7f_ffff;
+
+    if f32_exponent == 0xff {
+        // Infinity (mantissa == 0) or NaN (mantissa != 0); keep the mantissa
+        // non-zero so NaN doesn't collapse into infinity.
+        let half_mantissa = if mantissa == 0 { 0 } else { (mantissa >> 13).max(1) as u16 };
+        sign | 0x7c00 | half_mantissa
+    } else if exponent >= 0x1f {
+        sign | 0x7c00
+    } else if exponent <= 0 {
+        if exponent < -10 {
+            // Too small to represent even as an f16 subnormal; flush to zero.
+            sign
+        } else {
+            // Subnormal half: shift the implicit leading bit down into the
+            // mantissa by however far the exponent underflows zero.
+            let mantissa = (mantissa | 0x80_0000) >> (1 - exponent);
+            sign | ((mantissa >> 13) as u16)
+        }
+    } else {
+        sign | ((exponent as u16) << 10) | ((mantissa >> 13) as u16)
+    }
+}
+
+impl ConvertTo<f32> for F16 {
+    fn convert_to(self) -> f32 {
+        #[cfg(feature = "f16")]
+        { self.0.to_f32() }
+        #[cfg(not(feature = "f16"))]
+        { f16_bits_to_f32(self.0) }
+    }
+}
+
+impl ConvertTo<f64> for F16 {
+    fn convert_to(self) -> f64 {
+        ConvertTo::<f32>::convert_to(self) as f64
+    }
+}
+
+impl ConvertTo<F16> for f32 {
+    fn convert_to(self) -> F16 {
+        #[cfg(feature = "f16")]
+        { F16(half::f16::from_f32(self)) }
+        #[cfg(not(feature = "f16"))]
+        { F16(f32_to_f16_bits(self)) }
+    }
+}
+
+impl ConvertTo<F16> for f64 {
+    fn convert_to(self) -> F16 {
+        (self as f32).convert_to()
+    }
+}
+
+impl ops::Add for F16 {
+    type Output = F16;
+    fn add(self, rhs: F16) -> F16 {
+        (ConvertTo::<f32>::convert_to(self) + ConvertTo::<f32>::convert_to(rhs)).convert_to()
+    }
+}
+
+impl ops::Sub for F16 {
+    type Output = F16;
+    fn sub(self, rhs: F16) -> F16 {
+        (ConvertTo::<f32>::convert_to(self) - ConvertTo::<f32>::convert_to(rhs)).convert_to()
+    }
+}
+
+impl ops::Mul for F16 {
+    type Output = F16;
+    fn mul(self, rhs: F16) -> F16 {
+        (ConvertTo::<f32>::convert_to(self) * ConvertTo::<f32>::convert_to(rhs)).convert_to()
+    }
+}
+
+impl ops::Div for F16 {
+    type Output = F16;
+    fn div(self, rhs: F16) -> F16 {
+        (ConvertTo::<f32>::convert_to(self) / ConvertTo::<f32>::convert_to(rhs)).convert_to()
+    }
+}
+
+impl Scalar for F16 {
+    fn is_nan(self) -> bool {
+        ConvertTo::<f32>::convert_to(self).is_nan()
+    }
+
+    fn sqrt(self) -> Self {
+        ConvertTo::<f32>::convert_to(self).sqrt().convert_to()
+    }
+
+    fn recip(self) -> Self {
+        ConvertTo::<f32>::convert_to(self).recip().convert_to()
+    }
+}
+
+#[cfg(test)]
+mod f16_tests {
+    use crate::core::geometry::{ConvertTo, Scalar, F16};
+
+    #[test]
+    fn test_zero_round_trip() {
+        let half: F16 = 0.0f32.convert_to();
+        assert_eq!(ConvertTo::<f32>::convert_to(half), 0.0f32);
+    }
+
+    #[test]
+    fn test_nan_round_trip() {
+        let half: F16 = f32::NAN.convert_to();
+        assert!(half.is_nan());
+        assert!(ConvertTo::<f32>::convert_to(half).is_nan());
+    }
+
+    #[test]
+    fn test_infinity_round_trip() {
+        let half: F16 = f32::INFINITY.convert_to();
+        assert_eq!(ConvertTo::<f32>::convert_to(half), f32::INFINITY);
+
+        let neg_half: F16 = f32::NEG_INFINITY.convert_to();
+        assert_eq!(ConvertTo::<f32>::convert_to(neg_half), f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_subnormal_round_trip() {
+        // Smallest positive f16 subnormal is 2^-24.
+        let smallest_subnormal = 2.0f32.powi(-24);
+        let half: F16 = smallest_subnormal.convert_to();
+        assert_eq!(ConvertTo::<f32>::convert_to(half), smallest_subnormal);
+    }
+
+    #[test]
+    fn test_max_finite_round_trip() {
+        let max_f16_finite = 65504.0f32;
+        let half: F16 = max_f16_finite.convert_to();
+        assert_eq!(ConvertTo::<f32>::convert_to(half), max_f16_finite);
+    }
+}
+
 pub type Vector2i = Vector2<i32>;
 pub type Vector2f = Vector2<f32>;
+pub type Vector2h = Vector2<F16>;
 
 // We derive from PartialEq in order to use assert_eqs in tests.
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -14,7 +495,7 @@ pub struct Vector2<T> {
 }
 
 // This NaN-check is only implemented for Float-types.
-impl<T> Vector2<T> where T: Float {
+impl<T> Vector2<T> where T: Scalar {
     pub fn new(x: T, y: T) -> Self {
         assert!(
             !x.is_nan() && !y.is_nan()
@@ -28,7 +509,7 @@ impl<T> Vector2<T> {
         self.x * self.x + self.y * self.y
     }
 
-    pub fn length(self) -> T where T: num_traits::Float {
+    pub fn length(self) -> T where T: Scalar {
         self.length_squared().sqrt()
     }
 
@@ -43,6 +524,11 @@ impl<T> Vector2<T> {
     pub fn dot(self, rhs: Vector2<T>) -> T where T: ops::Mul<Output=T> + ops::Add<Output=T> {
         self.x * rhs.x + self.y * rhs.y
     }
+
+    /// Converts each component via `ConvertTo`, e.g. promoting a stored `F16` direction to `f32`.
+    pub fn to<U>(self) -> Vector2<U> where T: ConvertTo<U> {
+        Vector2 { x: self.x.convert_to(), y: self.y.convert_to() }
+    }
 }
 
 
@@ -78,7 +564,7 @@ impl<T> ops::SubAssign for Vector2<T> where T: ops::SubAssign {
     }
 }
 
-impl<T> ops::Mul<T> for Vector2<T> where T: Float {
+impl<T> ops::Mul<T> for Vector2<T> where T: Scalar {
     type Output = Vector2<T>;
 
     fn mul(self, rhs: T) -> Self::Output {
@@ -93,7 +579,7 @@ impl<T> ops::MulAssign<T> for Vector2<T> where T: Copy + ops::MulAssign {
     }
 }
 
-impl<T> ops::Div<T> for Vector2<T> where T: Float {
+impl<T> ops::Div<T> for Vector2<T> where T: Scalar {
     type Output = Vector2<T>;
 
     fn div(self, rhs: T) -> Vector2<T> {
@@ -283,6 +769,8 @@ mod vector2_tests {
     }
 }
 
+pub type Vector3h = Vector3<F16>;
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub struct Vector3<T> {
     pub x: T,
@@ -291,7 +779,7 @@ pub struct Vector3<T> {
 }
 
 // This NaN-check is only implemented for Float-types.
-impl<T> Vector3<T> where T: Float {
+impl<T> Vector3<T> where T: Scalar {
     pub fn new(x: T, y: T, z: T) -> Self {
         assert!(
             !x.is_nan() && !y.is_nan() && !z.is_nan()
@@ -305,7 +793,7 @@ impl<T> Vector3<T> {
         self.x * self.x + self.y * self.y + self.z * self.z
     }
 
-    pub fn length(self) -> T where T: num_traits::Float {
+    pub fn length(self) -> T where T: Scalar {
         self.length_squared().sqrt()
     }
 
@@ -320,6 +808,74 @@ impl<T> Vector3<T> {
     pub fn dot(self, rhs: Vector3<T>) -> T where T: ops::Mul<Output=T> + ops::Add<Output=T> {
         self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
     }
+
+    /// Cross product, computed via `difference_of_products` on each
+    /// component so a cancellation-resistant (e.g. FMA-based) implementation
+    /// can be swapped in later without touching call sites.
+    pub fn cross(self, rhs: Vector3<T>) -> Vector3<T> where T: ops::Mul<Output=T> + ops::Sub<Output=T> + Copy {
+        Vector3 {
+            x: difference_of_products(self.y, rhs.z, self.z, rhs.y),
+            y: difference_of_products(self.z, rhs.x, self.x, rhs.z),
+            z: difference_of_products(self.x, rhs.y, self.y, rhs.x),
+        }
+    }
+
+    pub fn normalize(self) -> Vector3<T> where T: Scalar {
+        self / self.length()
+    }
+
+    pub fn min_component(self) -> T where T: PartialOrd {
+        if self.x < self.y {
+            if self.x < self.z { self.x } else { self.z }
+        } else if self.y < self.z {
+            self.y
+        } else {
+            self.z
+        }
+    }
+
+    pub fn max_component(self) -> T where T: PartialOrd {
+        if self.x > self.y {
+            if self.x > self.z { self.x } else { self.z }
+        } else if self.y > self.z {
+            self.y
+        } else {
+            self.z
+        }
+    }
+
+    /// Index (0, 1, or 2) of the component with the largest value.
+    pub fn max_dimension(self) -> usize where T: PartialOrd {
+        if self.x > self.y {
+            if self.x > self.z { 0 } else { 2 }
+        } else if self.y > self.z {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Builds an orthonormal basis `(v2, v3)` from a single normalized
+    /// vector `self`. Whichever of `x`/`y` has the larger squared magnitude
+    /// is kept in `v2`'s plane together with `z`, which keeps `v2`'s
+    /// normalizing division well away from zero; `v3` is then `self` crossed
+    /// with `v2`.
+    pub fn coordinate_system(self) -> (Vector3<T>, Vector3<T>) where T: Scalar + PartialOrd + ops::Neg<Output=T> + num_traits::Zero {
+        let v2 = if self.x * self.x > self.y * self.y {
+            let len = (self.x * self.x + self.z * self.z).sqrt();
+            Vector3 { x: -self.z, y: T::zero(), z: self.x } / len
+        } else {
+            let len = (self.y * self.y + self.z * self.z).sqrt();
+            Vector3 { x: T::zero(), y: self.z, z: -self.y } / len
+        };
+        let v3 = self.cross(v2);
+        (v2, v3)
+    }
+
+    /// Converts each component via `ConvertTo`, e.g. narrowing a computed `f32` result back to `F16`.
+    pub fn to<U>(self) -> Vector3<U> where T: ConvertTo<U> {
+        Vector3 { x: self.x.convert_to(), y: self.y.convert_to(), z: self.z.convert_to() }
+    }
 }
 
 
@@ -357,7 +913,7 @@ impl<T> ops::SubAssign for Vector3<T> where T: ops::SubAssign {
     }
 }
 
-impl<T> ops::Mul<T> for Vector3<T> where T: Float {
+impl<T> ops::Mul<T> for Vector3<T> where T: Scalar {
     type Output = Vector3<T>;
 
     fn mul(self, rhs: T) -> Self::Output {
@@ -373,7 +929,7 @@ impl<T> ops::MulAssign<T> for Vector3<T> where T: Copy + ops::MulAssign {
     }
 }
 
-impl<T> ops::Div<T> for Vector3<T> where T: Float {
+impl<T> ops::Div<T> for Vector3<T> where T: Scalar {
     type Output = Vector3<T>;
 
     fn div(self, rhs: T) -> Vector3<T> {
@@ -406,13 +962,28 @@ pub fn vec3_dot<T>(a: Vector3<T>, b: Vector3<T>) -> T where T: ops::Mul<Output=T
     a.x * b.x + a.y * b.y + a.z + b.z
 }
 
+/// Computes `a * b - c * d`, grouped as its own helper so a cancellation-
+/// resistant implementation (e.g. FMA-based, as in pbrt's `DifferenceOfProducts`)
+/// can replace the naive subtraction later without touching call sites.
+fn difference_of_products<T>(a: T, b: T, c: T, d: T) -> T where T: ops::Mul<Output=T> + ops::Sub<Output=T> {
+    a * b - c * d
+}
+
+pub fn vec3_cross<T>(a: Vector3<T>, b: Vector3<T>) -> Vector3<T> where T: ops::Mul<Output=T> + ops::Sub<Output=T> + Copy {
+    a.cross(b)
+}
+
+pub fn vec3_normalize<T>(v: Vector3<T>) -> Vector3<T> where T: Scalar {
+    v.normalize()
+}
+
 #[cfg(test)]
 mod vector3_tests {
     use std::panic;
 
     use num_traits::Float;
 
-    use crate::core::geometry::{vec2_dot, vec3_dot, Vector2, Vector3};
+    use crate::core::geometry::{vec2_dot, vec3_cross, vec3_dot, vec3_normalize, Vector2, Vector3};
 
     #[test]
     fn test_constructor_nan_float() {
@@ -562,4 +1133,45 @@ mod vector3_tests {
         assert_eq!(vec3_dot(v1, v1), 5.0);
         assert_eq!(v1.dot(v1), 5.0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_cross() {
+        let x = Vector3 { x: 1.0, y: 0.0, z: 0.0 };
+        let y = Vector3 { x: 0.0, y: 1.0, z: 0.0 };
+        let z = Vector3 { x: 0.0, y: 0.0, z: 1.0 };
+
+        assert_eq!(x.cross(y), z);
+        assert_eq!(vec3_cross(x, y), z);
+        assert_eq!(y.cross(x), -z);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let v1 = Vector3 { x: 3.0, y: 0.0, z: 4.0 };
+        let normalized = v1.normalize();
+
+        assert_eq!(normalized.length(), 1.0);
+        assert_eq!(vec3_normalize(v1), normalized);
+    }
+
+    #[test]
+    fn test_min_max_component() {
+        let v1 = Vector3 { x: -1.0, y: 2.0, z: 0.5 };
+
+        assert_eq!(v1.min_component(), -1.0);
+        assert_eq!(v1.max_component(), 2.0);
+        assert_eq!(v1.max_dimension(), 1);
+    }
+
+    #[test]
+    fn test_coordinate_system() {
+        let v1 = Vector3 { x: 0.6, y: 0.0, z: 0.8 };
+        let (v2, v3) = v1.coordinate_system();
+
+        assert_eq!(v2.length(), 1.0);
+        assert_eq!(v3.length(), 1.0);
+        assert_eq!(v1.dot(v2), 0.0);
+        assert_eq!(v1.dot(v3), 0.0);
+        assert_eq!(v2.dot(v3), 0.0);
+    }
+}
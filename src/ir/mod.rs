@@ -0,0 +1,454 @@
+//! Deferred expression IR for batched vector math (`ir` cargo feature).
+//!
+//! A `Context` owns an arena of nodes; overloaded operators on `Value`
+//! append to the graph instead of evaluating eagerly, which can then be
+//! lowered to a closure evaluating over slices in SoA layout.
+#![cfg(feature = "ir")]
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ops;
+use std::rc::Rc;
+
+use typed_arena::Arena;
+
+use crate::core::geometry::{Vector2, Vector3};
+
+/// Scalar element type carried by a graph node.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ScalarType {
+    F32,
+    F64,
+    I32,
+}
+
+/// A fixed-width vector of some scalar element type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct VectorType {
+    pub element: ScalarType,
+    pub dim: u8,
+}
+
+/// A scalar or vector type carried by a graph `Value`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum NodeType {
+    Scalar(ScalarType),
+    Vector(VectorType),
+}
+
+impl NodeType {
+    fn element(self) -> ScalarType {
+        match self {
+            NodeType::Scalar(element) => element,
+            NodeType::Vector(VectorType { element, .. }) => element,
+        }
+    }
+}
+
+/// Opcode for a single IR node; operands are indices into the owning
+/// `Context`'s node list.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Op {
+    Input(usize),
+    Const(u64),
+    Add(usize, usize),
+    Sub(usize, usize),
+    Mul(usize, usize),
+    Neg(usize),
+    Sqrt(usize),
+}
+
+struct Node {
+    op: Op,
+    ty: NodeType,
+}
+
+struct ContextInner {
+    arena: Arena<Node>,
+    // Safety: `typed_arena::Arena` never moves or frees an item once
+    // allocated, so these pointers stay valid for the lifetime of the
+    // `Context` that owns `arena`.
+    nodes: Vec<*const Node>,
+    dedup: HashMap<Op, usize>,
+}
+
+impl ContextInner {
+    fn node(&self, index: usize) -> &Node {
+        unsafe { &*self.nodes[index] }
+    }
+
+    fn push(&mut self, op: Op, ty: NodeType) -> usize {
+        if let Some(existing) = self.dedup.get(&op) {
+            return *existing;
+        }
+        let node = self.arena.alloc(Node { op: op.clone(), ty });
+        let index = self.nodes.len();
+        self.nodes.push(node as *const Node);
+        self.dedup.insert(op, index);
+        index
+    }
+}
+
+/// Owns the arena of IR nodes for one expression graph. Cheap to clone.
+#[derive(Clone)]
+pub struct Context(Rc<RefCell<ContextInner>>);
+
+impl Default for Context {
+    fn default() -> Self {
+        Context::new()
+    }
+}
+
+impl Context {
+    pub fn new() -> Self {
+        Context(Rc::new(RefCell::new(ContextInner {
+            arena: Arena::new(),
+            nodes: Vec::new(),
+            dedup: HashMap::new(),
+        })))
+    }
+
+    /// Declares an input lane, e.g. one component of a batched `Vector3`.
+    pub fn input(&self, slot: usize, ty: NodeType) -> Value {
+        let index = self.0.borrow_mut().push(Op::Input(slot), ty);
+        Value { ctx: self.clone(), index }
+    }
+
+    fn constant(&self, bits: u64, element: ScalarType) -> Value {
+        let index = self.0.borrow_mut().push(Op::Const(bits), NodeType::Scalar(element));
+        Value { ctx: self.clone(), index }
+    }
+
+    /// Evaluates every node topologically (node `i` only ever depends on
+    /// nodes `< i`, since nodes are only ever appended) over `lane_count`
+    /// lanes, writing each node's result into a scratch buffer, and returns
+    /// the lane buffer for `output`. Each node is evaluated in its own
+    /// scalar type (see `Lane`), rather than being hard-coded to `f32`.
+    pub fn lower_to_closure(&self, output: Value) -> impl Fn(&[Lane], usize) -> Lane {
+        let ctx = self.clone();
+        move |inputs: &[Lane], lane_count: usize| {
+            let inner = ctx.0.borrow();
+            let mut scratch: Vec<Lane> = Vec::with_capacity(inner.nodes.len());
+            for index in 0..inner.nodes.len() {
+                let node = inner.node(index);
+                let lane = match &node.op {
+                    Op::Input(slot) => {
+                        let lane = inputs[*slot].clone();
+                        assert_eq!(
+                            lane.len(),
+                            lane_count,
+                            "input slot {slot} has {} lanes, expected {lane_count}",
+                            lane.len()
+                        );
+                        lane
+                    }
+                    Op::Const(bits) => Lane::splat(node.ty.element(), *bits, lane_count),
+                    Op::Add(a, b) => scratch[*a].zip_with(&scratch[*b], |x, y| x + y, |x, y| x + y, i32::wrapping_add),
+                    Op::Sub(a, b) => scratch[*a].zip_with(&scratch[*b], |x, y| x - y, |x, y| x - y, i32::wrapping_sub),
+                    Op::Mul(a, b) => scratch[*a].zip_with(&scratch[*b], |x, y| x * y, |x, y| x * y, i32::wrapping_mul),
+                    Op::Neg(a) => scratch[*a].map(|x| -x, |x| -x, i32::wrapping_neg),
+                    Op::Sqrt(a) => scratch[*a].map(|x| x.sqrt(), |x| x.sqrt(), |x| (x as f64).sqrt() as i32),
+                };
+                scratch.push(lane);
+            }
+            scratch[output.index].clone()
+        }
+    }
+
+    /// Renders the graph as one line per node, for debugging.
+    pub fn pretty_print(&self) -> String {
+        let inner = self.0.borrow();
+        let mut out = String::new();
+        for index in 0..inner.nodes.len() {
+            let node = inner.node(index);
+            out.push_str(&format!("%{} = {:?} : {:?}\n", index, node.op, node.ty));
+        }
+        out
+    }
+}
+
+/// A per-lane scratch buffer of one scalar type, as produced/consumed by `lower_to_closure`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Lane {
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+    I32(Vec<i32>),
+}
+
+impl Lane {
+    fn splat(element: ScalarType, bits: u64, lane_count: usize) -> Lane {
+        match element {
+            ScalarType::F32 => Lane::F32(vec![f32::from_bits(bits as u32); lane_count]),
+            ScalarType::F64 => Lane::F64(vec![f64::from_bits(bits); lane_count]),
+            ScalarType::I32 => Lane::I32(vec![(bits as u32) as i32; lane_count]),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Lane::F32(v) => v.len(),
+            Lane::F64(v) => v.len(),
+            Lane::I32(v) => v.len(),
+        }
+    }
+
+    fn map(&self, f32: impl Fn(f32) -> f32, f64: impl Fn(f64) -> f64, i32: impl Fn(i32) -> i32) -> Lane {
+        match self {
+            Lane::F32(v) => Lane::F32(v.iter().map(|&x| f32(x)).collect()),
+            Lane::F64(v) => Lane::F64(v.iter().map(|&x| f64(x)).collect()),
+            Lane::I32(v) => Lane::I32(v.iter().map(|&x| i32(x)).collect()),
+        }
+    }
+
+    fn zip_with(
+        &self,
+        rhs: &Lane,
+        f32: impl Fn(f32, f32) -> f32,
+        f64: impl Fn(f64, f64) -> f64,
+        i32: impl Fn(i32, i32) -> i32,
+    ) -> Lane {
+        match (self, rhs) {
+            (Lane::F32(a), Lane::F32(b)) => Lane::F32(a.iter().zip(b).map(|(&x, &y)| f32(x, y)).collect()),
+            (Lane::F64(a), Lane::F64(b)) => Lane::F64(a.iter().zip(b).map(|(&x, &y)| f64(x, y)).collect()),
+            (Lane::I32(a), Lane::I32(b)) => Lane::I32(a.iter().zip(b).map(|(&x, &y)| i32(x, y)).collect()),
+            _ => panic!("mismatched lane types in binary ir op"),
+        }
+    }
+
+    pub fn as_f32(&self) -> &[f32] {
+        match self {
+            Lane::F32(v) => v,
+            _ => panic!("lane is not F32"),
+        }
+    }
+
+    pub fn as_f64(&self) -> &[f64] {
+        match self {
+            Lane::F64(v) => v,
+            _ => panic!("lane is not F64"),
+        }
+    }
+
+    pub fn as_i32(&self) -> &[i32] {
+        match self {
+            Lane::I32(v) => v,
+            _ => panic!("lane is not I32"),
+        }
+    }
+}
+
+/// Handle to a node in a `Context`'s expression graph. Only meaningful
+/// relative to the `Context` that produced it.
+#[derive(Clone)]
+pub struct Value {
+    ctx: Context,
+    index: usize,
+}
+
+impl Value {
+    fn ty(&self) -> NodeType {
+        self.ctx.0.borrow().node(self.index).ty
+    }
+
+    fn push(&self, op: Op, ty: NodeType) -> Value {
+        let index = self.ctx.0.borrow_mut().push(op, ty);
+        Value { ctx: self.ctx.clone(), index }
+    }
+
+    pub fn sqrt(&self) -> Value {
+        let ty = self.ty();
+        self.push(Op::Sqrt(self.index), ty)
+    }
+}
+
+impl ops::Add for Value {
+    type Output = Value;
+    fn add(self, rhs: Value) -> Value {
+        let ty = self.ty();
+        self.push(Op::Add(self.index, rhs.index), ty)
+    }
+}
+
+impl ops::Sub for Value {
+    type Output = Value;
+    fn sub(self, rhs: Value) -> Value {
+        let ty = self.ty();
+        self.push(Op::Sub(self.index, rhs.index), ty)
+    }
+}
+
+impl ops::Mul for Value {
+    type Output = Value;
+    fn mul(self, rhs: Value) -> Value {
+        let ty = self.ty();
+        self.push(Op::Mul(self.index, rhs.index), ty)
+    }
+}
+
+impl ops::Neg for Value {
+    type Output = Value;
+    fn neg(self) -> Value {
+        let ty = self.ty();
+        self.push(Op::Neg(self.index), ty)
+    }
+}
+
+/// Lifts an eager constant or `Vector2`/`Vector3` into `Value`(s) owned by `ctx`.
+pub trait Make<T> {
+    fn make(ctx: &Context, value: T) -> Self;
+}
+
+impl Make<f32> for Value {
+    fn make(ctx: &Context, value: f32) -> Value {
+        ctx.constant(value.to_bits() as u64, ScalarType::F32)
+    }
+}
+
+impl Make<f64> for Value {
+    fn make(ctx: &Context, value: f64) -> Value {
+        ctx.constant(value.to_bits(), ScalarType::F64)
+    }
+}
+
+impl Make<i32> for Value {
+    fn make(ctx: &Context, value: i32) -> Value {
+        ctx.constant((value as u32) as u64, ScalarType::I32)
+    }
+}
+
+/// A `Vector2` lifted into the graph: one per-lane `Value` per component.
+pub struct VecValue2 {
+    pub x: Value,
+    pub y: Value,
+}
+
+impl Make<Vector2<f32>> for VecValue2 {
+    fn make(ctx: &Context, value: Vector2<f32>) -> Self {
+        VecValue2 { x: Value::make(ctx, value.x), y: Value::make(ctx, value.y) }
+    }
+}
+
+impl VecValue2 {
+    /// Per-lane dot product, built from the component `Value`s.
+    pub fn dot(&self, rhs: &VecValue2) -> Value {
+        self.x.clone() * rhs.x.clone() + self.y.clone() * rhs.y.clone()
+    }
+
+    /// Per-lane length, i.e. `sqrt(self.dot(self))`.
+    pub fn length(&self) -> Value {
+        self.dot(self).sqrt()
+    }
+}
+
+/// A `Vector3` lifted into the graph: one per-lane `Value` per component.
+pub struct VecValue3 {
+    pub x: Value,
+    pub y: Value,
+    pub z: Value,
+}
+
+impl Make<Vector3<f32>> for VecValue3 {
+    fn make(ctx: &Context, value: Vector3<f32>) -> Self {
+        VecValue3 {
+            x: Value::make(ctx, value.x),
+            y: Value::make(ctx, value.y),
+            z: Value::make(ctx, value.z),
+        }
+    }
+}
+
+impl VecValue3 {
+    /// Per-lane dot product, built from the component `Value`s.
+    pub fn dot(&self, rhs: &VecValue3) -> Value {
+        self.x.clone() * rhs.x.clone() + self.y.clone() * rhs.y.clone() + self.z.clone() * rhs.z.clone()
+    }
+
+    /// Per-lane length, i.e. `sqrt(self.dot(self))`.
+    pub fn length(&self) -> Value {
+        self.dot(self).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::geometry::Vector3;
+    use crate::ir::{Context, Lane, Make, NodeType, ScalarType, Value, VecValue3};
+
+    #[test]
+    fn test_add_lower() {
+        let ctx = Context::new();
+        let a = Value::make(&ctx, 2.0f32);
+        let b = ctx.input(0, NodeType::Scalar(ScalarType::F32));
+        let sum = a + b;
+        let eval = ctx.lower_to_closure(sum);
+
+        let result = eval(&[Lane::F32(vec![1.0, 3.0])], 2);
+        assert_eq!(result.as_f32(), &[3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_f64_graph_stays_f64_end_to_end() {
+        let ctx = Context::new();
+        let a = Value::make(&ctx, 2.0f64);
+        let b = ctx.input(0, NodeType::Scalar(ScalarType::F64));
+        let product = a * b;
+        let eval = ctx.lower_to_closure(product);
+
+        let result = eval(&[Lane::F64(vec![1.5, 2.5])], 2);
+        assert_eq!(result.as_f64(), &[3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_i32_graph() {
+        let ctx = Context::new();
+        let a = Value::make(&ctx, 3i32);
+        let b = Value::make(&ctx, 4i32);
+        let sum = a + b;
+        let eval = ctx.lower_to_closure(sum);
+
+        let result = eval(&[], 1);
+        assert_eq!(result.as_i32(), &[7]);
+    }
+
+    #[test]
+    #[should_panic(expected = "has 1 lanes, expected 2")]
+    fn test_input_length_mismatch_panics() {
+        let ctx = Context::new();
+        let input = ctx.input(0, NodeType::Scalar(ScalarType::F32));
+        let eval = ctx.lower_to_closure(input);
+
+        eval(&[Lane::F32(vec![1.0])], 2);
+    }
+
+    #[test]
+    fn test_vec3_dot_orthogonal() {
+        let ctx = Context::new();
+        let a = VecValue3::make(&ctx, Vector3 { x: 1.0, y: 0.0, z: 0.0 });
+        let b = VecValue3::make(&ctx, Vector3 { x: 0.0, y: 1.0, z: 0.0 });
+        let dot = a.dot(&b);
+        let eval = ctx.lower_to_closure(dot);
+
+        assert_eq!(eval(&[], 1).as_f32(), &[0.0]);
+    }
+
+    #[test]
+    fn test_vec3_length() {
+        let ctx = Context::new();
+        let v = VecValue3::make(&ctx, Vector3 { x: 3.0, y: 0.0, z: 4.0 });
+        let length = v.length();
+        let eval = ctx.lower_to_closure(length);
+
+        assert_eq!(eval(&[], 1).as_f32(), &[5.0]);
+    }
+
+    #[test]
+    fn test_cse_dedups_identical_nodes() {
+        let ctx = Context::new();
+        let a = Value::make(&ctx, 2.0f32);
+        let b = Value::make(&ctx, 2.0f32);
+        let _ = a + b;
+
+        // Two identical `Const(2.0)` nodes should dedup to a single node.
+        assert_eq!(ctx.pretty_print().lines().count(), 2);
+    }
+}